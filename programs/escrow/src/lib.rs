@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("33333333333333333333333333333333");
 
@@ -6,11 +7,216 @@ declare_id!("33333333333333333333333333333333");
 mod fartnode_escrow {
 	use super::*;
 
-	pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-		msg!("FARTNODE escrow program stub");
+	pub fn initialize_escrow(ctx: Context<InitializeEscrow>, amount: u64) -> Result<()> {
+		let escrow = &mut ctx.accounts.escrow;
+		escrow.initializer = ctx.accounts.initializer.key();
+		escrow.counterparty = ctx.accounts.counterparty.key();
+		escrow.mint = ctx.accounts.mint.key();
+		escrow.amount = amount;
+		escrow.settled = false;
+		escrow.bump = ctx.bumps.vault_authority;
+
+		let cpi_accounts = Transfer {
+			from: ctx.accounts.initializer_token_account.to_account_info(),
+			to: ctx.accounts.vault.to_account_info(),
+			authority: ctx.accounts.initializer.to_account_info(),
+		};
+		token::transfer(
+			CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+			amount,
+		)?;
+
 		Ok(())
 	}
+
+	pub fn release(ctx: Context<Release>) -> Result<()> {
+		let escrow_key = ctx.accounts.escrow.key();
+		let escrow = &mut ctx.accounts.escrow;
+		require!(!escrow.settled, EscrowError::AlreadySettled);
+		require_keys_eq!(ctx.accounts.mint.key(), escrow.mint, EscrowError::WrongMint);
+
+		let amount = escrow.amount;
+		escrow.settled = true;
+
+		let seeds = &[b"vault".as_ref(), escrow_key.as_ref(), &[escrow.bump]];
+		let signer_seeds = &[&seeds[..]];
+		let cpi_accounts = Transfer {
+			from: ctx.accounts.vault.to_account_info(),
+			to: ctx.accounts.counterparty_token_account.to_account_info(),
+			authority: ctx.accounts.vault_authority.to_account_info(),
+		};
+		token::transfer(
+			CpiContext::new_with_signer(
+				ctx.accounts.token_program.to_account_info(),
+				cpi_accounts,
+				signer_seeds,
+			),
+			amount,
+		)?;
+
+		emit!(EscrowReleased {
+			escrow: escrow_key,
+			counterparty: ctx.accounts.escrow.counterparty,
+			amount,
+		});
+
+		Ok(())
+	}
+
+	pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+		let escrow_key = ctx.accounts.escrow.key();
+		let escrow = &mut ctx.accounts.escrow;
+		require!(!escrow.settled, EscrowError::AlreadySettled);
+		require_keys_eq!(ctx.accounts.mint.key(), escrow.mint, EscrowError::WrongMint);
+
+		let amount = escrow.amount;
+		escrow.settled = true;
+
+		let seeds = &[b"vault".as_ref(), escrow_key.as_ref(), &[escrow.bump]];
+		let signer_seeds = &[&seeds[..]];
+		let cpi_accounts = Transfer {
+			from: ctx.accounts.vault.to_account_info(),
+			to: ctx.accounts.initializer_token_account.to_account_info(),
+			authority: ctx.accounts.vault_authority.to_account_info(),
+		};
+		token::transfer(
+			CpiContext::new_with_signer(
+				ctx.accounts.token_program.to_account_info(),
+				cpi_accounts,
+				signer_seeds,
+			),
+			amount,
+		)?;
+
+		emit!(EscrowCancelled {
+			escrow: escrow_key,
+			initializer: ctx.accounts.escrow.initializer,
+			amount,
+		});
+
+		Ok(())
+	}
+}
+
+#[derive(Accounts)]
+pub struct InitializeEscrow<'info> {
+	#[account(
+		init,
+		payer = initializer,
+		space = 8 + Escrow::INIT_SPACE,
+		seeds = [b"escrow", initializer.key().as_ref(), counterparty.key().as_ref(), mint.key().as_ref()],
+		bump
+	)]
+	pub escrow: Account<'info, Escrow>,
+
+	/// CHECK: the PDA that owns the vault token account; never read, only signs via seeds
+	#[account(seeds = [b"vault", escrow.key().as_ref()], bump)]
+	pub vault_authority: UncheckedAccount<'info>,
+
+	#[account(
+		init,
+		payer = initializer,
+		token::mint = mint,
+		token::authority = vault_authority,
+		seeds = [b"vault_token", escrow.key().as_ref()],
+		bump
+	)]
+	pub vault: Account<'info, TokenAccount>,
+
+	#[account(mut, token::mint = mint, token::authority = initializer)]
+	pub initializer_token_account: Account<'info, TokenAccount>,
+
+	pub mint: Account<'info, Mint>,
+
+	#[account(mut)]
+	pub initializer: Signer<'info>,
+
+	/// CHECK: only stored on the escrow account, never read
+	pub counterparty: UncheckedAccount<'info>,
+
+	pub token_program: Program<'info, Token>,
+	pub system_program: Program<'info, System>,
 }
 
+// `release` pays out to the counterparty, so the counterparty must be the one
+// to signal satisfaction and sign for it; the initializer has no say here
+// beyond having funded the escrow. `cancel`, by contrast, only ever returns
+// funds to the initializer, so it stays gated on the initializer alone.
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct Release<'info> {
+	#[account(mut, has_one = counterparty @ EscrowError::WrongCounterparty)]
+	pub escrow: Account<'info, Escrow>,
+
+	/// CHECK: the PDA that owns the vault token account, derived from `escrow.bump`
+	#[account(seeds = [b"vault", escrow.key().as_ref()], bump = escrow.bump)]
+	pub vault_authority: UncheckedAccount<'info>,
+
+	#[account(mut, seeds = [b"vault_token", escrow.key().as_ref()], bump)]
+	pub vault: Account<'info, TokenAccount>,
+
+	#[account(mut, token::mint = mint, token::authority = counterparty)]
+	pub counterparty_token_account: Account<'info, TokenAccount>,
+
+	pub mint: Account<'info, Mint>,
+
+	pub counterparty: Signer<'info>,
+
+	pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+	#[account(mut, has_one = initializer)]
+	pub escrow: Account<'info, Escrow>,
+
+	/// CHECK: the PDA that owns the vault token account, derived from `escrow.bump`
+	#[account(seeds = [b"vault", escrow.key().as_ref()], bump = escrow.bump)]
+	pub vault_authority: UncheckedAccount<'info>,
+
+	#[account(mut, seeds = [b"vault_token", escrow.key().as_ref()], bump)]
+	pub vault: Account<'info, TokenAccount>,
+
+	#[account(mut, token::mint = mint, token::authority = initializer)]
+	pub initializer_token_account: Account<'info, TokenAccount>,
+
+	pub mint: Account<'info, Mint>,
+
+	pub initializer: Signer<'info>,
+
+	pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+	pub initializer: Pubkey,
+	pub counterparty: Pubkey,
+	pub mint: Pubkey,
+	pub amount: u64,
+	pub settled: bool,
+	pub bump: u8,
+}
+
+#[event]
+pub struct EscrowReleased {
+	pub escrow: Pubkey,
+	pub counterparty: Pubkey,
+	pub amount: u64,
+}
+
+#[event]
+pub struct EscrowCancelled {
+	pub escrow: Pubkey,
+	pub initializer: Pubkey,
+	pub amount: u64,
+}
+
+#[error_code]
+pub enum EscrowError {
+	#[msg("Token account mint does not match the escrow's mint")]
+	WrongMint,
+	#[msg("Signer is not the escrow counterparty")]
+	WrongCounterparty,
+	#[msg("Escrow has already been settled")]
+	AlreadySettled,
+}
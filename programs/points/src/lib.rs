@@ -2,15 +2,186 @@ use anchor_lang::prelude::*;
 
 declare_id!("11111111111111111111111111111111");
 
+/// The only program ever allowed to initialize `PointsConfig`. `config` is a
+/// singleton PDA, so without this check whoever calls `initialize` first —
+/// not necessarily through `fartnode_quests` — would permanently become
+/// `config.authority`. Pinning `authority` to the quests program's signing
+/// PDA means only a `mint_points`-composing CPI from that program can ever
+/// win the init race, since no other program can produce that PDA's
+/// signature.
+pub const QUESTS_PROGRAM_ID: Pubkey = pubkey!("22222222222222222222222222222222");
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
 #[program]
 mod fartnode_points {
 	use super::*;
 
-	pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-		msg!("FARTNODE points program stub");
+	pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+		let (expected_authority, _bump) =
+			Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], &QUESTS_PROGRAM_ID);
+		require_keys_eq!(
+			ctx.accounts.authority.key(),
+			expected_authority,
+			PointsError::Unauthorized
+		);
+
+		let config = &mut ctx.accounts.config;
+		config.authority = ctx.accounts.authority.key();
+		config.total_supply = 0;
+		msg!("FARTNODE points program initialized");
 		Ok(())
 	}
+
+	pub fn mint_points(ctx: Context<MintPoints>, amount: u64) -> Result<()> {
+		require_keys_eq!(
+			ctx.accounts.config.authority,
+			ctx.accounts.authority.key(),
+			PointsError::Unauthorized
+		);
+
+		let points_account = &mut ctx.accounts.points_account;
+		points_account.owner = ctx.accounts.user.key();
+		points_account.balance = points_account
+			.balance
+			.checked_add(amount)
+			.ok_or(PointsError::Overflow)?;
+
+		let config = &mut ctx.accounts.config;
+		config.total_supply = config
+			.total_supply
+			.checked_add(amount)
+			.ok_or(PointsError::Overflow)?;
+
+		emit!(PointsMinted {
+			user: ctx.accounts.user.key(),
+			amount,
+			new_balance: ctx.accounts.points_account.balance,
+		});
+
+		Ok(())
+	}
+
+	pub fn burn_points(ctx: Context<BurnPoints>, amount: u64) -> Result<()> {
+		require_keys_eq!(
+			ctx.accounts.config.authority,
+			ctx.accounts.authority.key(),
+			PointsError::Unauthorized
+		);
+
+		let points_account = &mut ctx.accounts.points_account;
+		points_account.balance = points_account
+			.balance
+			.checked_sub(amount)
+			.ok_or(PointsError::Underflow)?;
+
+		let config = &mut ctx.accounts.config;
+		config.total_supply = config
+			.total_supply
+			.checked_sub(amount)
+			.ok_or(PointsError::Underflow)?;
+
+		emit!(PointsBurned {
+			user: ctx.accounts.user.key(),
+			amount,
+			new_balance: ctx.accounts.points_account.balance,
+		});
+
+		Ok(())
+	}
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+	#[account(
+		init,
+		payer = authority,
+		space = 8 + PointsConfig::INIT_SPACE,
+		seeds = [b"config"],
+		bump
+	)]
+	pub config: Account<'info, PointsConfig>,
+
+	#[account(mut)]
+	pub authority: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct MintPoints<'info> {
+	#[account(mut, seeds = [b"config"], bump)]
+	pub config: Account<'info, PointsConfig>,
+
+	#[account(
+		init_if_needed,
+		payer = authority,
+		space = 8 + PointsAccount::INIT_SPACE,
+		seeds = [b"points", user.key().as_ref()],
+		bump
+	)]
+	pub points_account: Account<'info, PointsAccount>,
+
+	/// CHECK: only used as a seed and to set `PointsAccount::owner`
+	pub user: UncheckedAccount<'info>,
+
+	#[account(mut)]
+	pub authority: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnPoints<'info> {
+	#[account(mut, seeds = [b"config"], bump)]
+	pub config: Account<'info, PointsConfig>,
+
+	#[account(
+		mut,
+		seeds = [b"points", user.key().as_ref()],
+		bump
+	)]
+	pub points_account: Account<'info, PointsAccount>,
+
+	/// CHECK: only used as a seed
+	pub user: UncheckedAccount<'info>,
+
+	pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PointsConfig {
+	pub authority: Pubkey,
+	pub total_supply: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PointsAccount {
+	pub owner: Pubkey,
+	pub balance: u64,
+}
+
+#[event]
+pub struct PointsMinted {
+	pub user: Pubkey,
+	pub amount: u64,
+	pub new_balance: u64,
+}
+
+#[event]
+pub struct PointsBurned {
+	pub user: Pubkey,
+	pub amount: u64,
+	pub new_balance: u64,
+}
+
+#[error_code]
+pub enum PointsError {
+	#[msg("Signer is not the points program authority")]
+	Unauthorized,
+	#[msg("Points balance overflowed")]
+	Overflow,
+	#[msg("Points balance underflowed")]
+	Underflow,
+}
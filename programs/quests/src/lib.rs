@@ -1,16 +1,257 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use points::cpi::accounts::Initialize as PointsInitialize;
+use points::cpi::accounts::MintPoints;
+use points::cpi::{initialize as points_initialize, mint_points};
+use points::program::Points;
+use points::PointsConfig;
 
 declare_id!("22222222222222222222222222222222");
 
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
 #[program]
 mod fartnode_quests {
 	use super::*;
 
-	pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-		msg!("FARTNODE quests program stub");
+	// `points::initialize` only accepts `mint_authority` (the PDA derived
+	// here from `MINT_AUTHORITY_SEED` and this program's id) as `authority`,
+	// so nobody can front-run this call and grab `config.authority` for
+	// themselves — no other program can produce that PDA's signature.
+	pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+		let bump = ctx.bumps.mint_authority;
+		let seeds = &[MINT_AUTHORITY_SEED, &[bump]];
+		let signer_seeds = &[&seeds[..]];
+
+		// `mint_authority` is a data-less PDA; fund it so it can act as the
+		// payer for `points::initialize`'s `PointsConfig` account.
+		system_program::transfer(
+			CpiContext::new(
+				ctx.accounts.system_program.to_account_info(),
+				Transfer {
+					from: ctx.accounts.payer.to_account_info(),
+					to: ctx.accounts.mint_authority.to_account_info(),
+				},
+			),
+			Rent::get()?.minimum_balance(8 + PointsConfig::INIT_SPACE),
+		)?;
+
+		let cpi_accounts = PointsInitialize {
+			config: ctx.accounts.points_config.to_account_info(),
+			authority: ctx.accounts.mint_authority.to_account_info(),
+			system_program: ctx.accounts.system_program.to_account_info(),
+		};
+		let cpi_ctx = CpiContext::new_with_signer(
+			ctx.accounts.points_program.to_account_info(),
+			cpi_accounts,
+			signer_seeds,
+		);
+		points_initialize(cpi_ctx)?;
+
+		msg!("FARTNODE quests program initialized");
+		Ok(())
+	}
+
+	pub fn create_quest(
+		ctx: Context<CreateQuest>,
+		quest_id: u64,
+		reward_points: u64,
+		max_completions: u32,
+	) -> Result<()> {
+		let quest = &mut ctx.accounts.quest;
+		quest.creator = ctx.accounts.creator.key();
+		quest.quest_id = quest_id;
+		quest.reward_points = reward_points;
+		quest.max_completions = max_completions;
+		quest.completions = 0;
+		quest.active = true;
+		Ok(())
+	}
+
+	pub fn set_active(ctx: Context<SetActive>, active: bool) -> Result<()> {
+		require_keys_eq!(
+			ctx.accounts.quest.creator,
+			ctx.accounts.creator.key(),
+			QuestsError::Unauthorized
+		);
+
+		ctx.accounts.quest.active = active;
+		Ok(())
+	}
+
+	pub fn complete_quest(ctx: Context<CompleteQuest>) -> Result<()> {
+		let quest = &mut ctx.accounts.quest;
+		require!(quest.active, QuestsError::QuestNotActive);
+		require!(
+			quest.completions < quest.max_completions,
+			QuestsError::QuestFull
+		);
+
+		quest.completions = quest
+			.completions
+			.checked_add(1)
+			.ok_or(QuestsError::Overflow)?;
+
+		let completion = &mut ctx.accounts.completion;
+		completion.quest = quest.key();
+		completion.user = ctx.accounts.user.key();
+
+		let bump = ctx.bumps.mint_authority;
+		let seeds = &[MINT_AUTHORITY_SEED, &[bump]];
+		let signer_seeds = &[&seeds[..]];
+
+		// `mint_authority` pays for a first-time user's `PointsAccount` in the
+		// CPI below; the user funds that rent through it.
+		if ctx.accounts.points_account.data_is_empty() {
+			system_program::transfer(
+				CpiContext::new(
+					ctx.accounts.system_program.to_account_info(),
+					Transfer {
+						from: ctx.accounts.user.to_account_info(),
+						to: ctx.accounts.mint_authority.to_account_info(),
+					},
+				),
+				Rent::get()?.minimum_balance(8 + points::PointsAccount::INIT_SPACE),
+			)?;
+		}
+
+		let cpi_accounts = MintPoints {
+			config: ctx.accounts.points_config.to_account_info(),
+			points_account: ctx.accounts.points_account.to_account_info(),
+			user: ctx.accounts.user.to_account_info(),
+			authority: ctx.accounts.mint_authority.to_account_info(),
+			system_program: ctx.accounts.system_program.to_account_info(),
+		};
+		let cpi_ctx = CpiContext::new_with_signer(
+			ctx.accounts.points_program.to_account_info(),
+			cpi_accounts,
+			signer_seeds,
+		);
+		mint_points(cpi_ctx, quest.reward_points)?;
+
+		emit!(QuestCompleted {
+			quest_id: quest.quest_id,
+			user: ctx.accounts.user.key(),
+			reward: quest.reward_points,
+		});
+
 		Ok(())
 	}
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct Initialize<'info> {
+	/// CHECK: data-less PDA that signs into the points program as its
+	/// registered mint authority; never deserialized, only signs via seeds
+	#[account(mut, seeds = [MINT_AUTHORITY_SEED], bump)]
+	pub mint_authority: UncheckedAccount<'info>,
+
+	/// CHECK: not yet initialized here; created by the CPI into `points::initialize`
+	#[account(mut)]
+	pub points_config: UncheckedAccount<'info>,
+
+	#[account(mut)]
+	pub payer: Signer<'info>,
+
+	pub points_program: Program<'info, Points>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(quest_id: u64)]
+pub struct CreateQuest<'info> {
+	#[account(
+		init,
+		payer = creator,
+		space = 8 + Quest::INIT_SPACE,
+		seeds = [b"quest", quest_id.to_le_bytes().as_ref()],
+		bump
+	)]
+	pub quest: Account<'info, Quest>,
+
+	#[account(mut)]
+	pub creator: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetActive<'info> {
+	#[account(mut)]
+	pub quest: Account<'info, Quest>,
+
+	pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteQuest<'info> {
+	#[account(mut)]
+	pub quest: Account<'info, Quest>,
+
+	#[account(
+		init,
+		payer = user,
+		space = 8 + QuestCompletion::INIT_SPACE,
+		seeds = [b"completion", quest.key().as_ref(), user.key().as_ref()],
+		bump
+	)]
+	pub completion: Account<'info, QuestCompletion>,
+
+	#[account(mut)]
+	pub user: Signer<'info>,
+
+	#[account(mut)]
+	pub points_config: Account<'info, PointsConfig>,
+
+	/// CHECK: may not yet be initialized for first-time users; the points
+	/// program's own `init_if_needed` validates and creates it during the CPI
+	#[account(mut)]
+	pub points_account: UncheckedAccount<'info>,
+
+	/// CHECK: data-less PDA registered as `points_config.authority`; signs
+	/// the `mint_points` CPI via seeds, no outside signer required
+	#[account(mut, seeds = [MINT_AUTHORITY_SEED], bump)]
+	pub mint_authority: UncheckedAccount<'info>,
+
+	pub points_program: Program<'info, Points>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Quest {
+	pub creator: Pubkey,
+	pub quest_id: u64,
+	pub reward_points: u64,
+	pub max_completions: u32,
+	pub completions: u32,
+	pub active: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct QuestCompletion {
+	pub quest: Pubkey,
+	pub user: Pubkey,
+}
+
+#[event]
+pub struct QuestCompleted {
+	pub quest_id: u64,
+	pub user: Pubkey,
+	pub reward: u64,
+}
+
+#[error_code]
+pub enum QuestsError {
+	#[msg("Signer is not the quest creator")]
+	Unauthorized,
+	#[msg("Quest is not active")]
+	QuestNotActive,
+	#[msg("Quest has reached its max completions")]
+	QuestFull,
+	#[msg("Completion counter overflowed")]
+	Overflow,
+}
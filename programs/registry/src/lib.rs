@@ -2,15 +2,175 @@ use anchor_lang::prelude::*;
 
 declare_id!("44444444444444444444444444444444");
 
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// The only signer allowed to call `initialize`. `config` is a singleton
+/// PDA, so without this check whoever calls `initialize` first would
+/// permanently become `config.authority` — with full `update_metadata`/
+/// `transfer_ownership`/`set_status` rights over every registered node, not
+/// just ones they created. Replace with the real deployment admin key before
+/// going live.
+pub const REGISTRY_INITIALIZER: Pubkey = pubkey!("55555555555555555555555555555555");
+
 #[program]
 mod fartnode_registry {
 	use super::*;
 
-	pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-		msg!("FARTNODE registry program stub");
+	pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+		require_keys_eq!(
+			ctx.accounts.authority.key(),
+			REGISTRY_INITIALIZER,
+			RegistryError::Unauthorized
+		);
+
+		ctx.accounts.config.authority = ctx.accounts.authority.key();
+		msg!("FARTNODE registry program initialized");
+		Ok(())
+	}
+
+	pub fn register_node(
+		ctx: Context<RegisterNode>,
+		node: Pubkey,
+		metadata_uri: String,
+	) -> Result<()> {
+		require!(
+			metadata_uri.len() <= MAX_METADATA_URI_LEN,
+			RegistryError::MetadataUriTooLong
+		);
+
+		let record = &mut ctx.accounts.node_record;
+		record.owner = ctx.accounts.owner.key();
+		record.node = node;
+		record.metadata_uri = metadata_uri;
+		record.registered_at = Clock::get()?.unix_timestamp;
+		record.status = NodeStatus::Active;
+
+		emit!(NodeRegistered {
+			node,
+			owner: record.owner,
+		});
+
 		Ok(())
 	}
+
+	pub fn update_metadata(ctx: Context<MutateNodeRecord>, metadata_uri: String) -> Result<()> {
+		require!(
+			metadata_uri.len() <= MAX_METADATA_URI_LEN,
+			RegistryError::MetadataUriTooLong
+		);
+		require_authorized(&ctx.accounts.node_record, &ctx.accounts.config, ctx.accounts.signer.key)?;
+
+		ctx.accounts.node_record.metadata_uri = metadata_uri;
+		Ok(())
+	}
+
+	pub fn transfer_ownership(ctx: Context<MutateNodeRecord>, new_owner: Pubkey) -> Result<()> {
+		require_authorized(&ctx.accounts.node_record, &ctx.accounts.config, ctx.accounts.signer.key)?;
+
+		ctx.accounts.node_record.owner = new_owner;
+		Ok(())
+	}
+
+	pub fn set_status(ctx: Context<MutateNodeRecord>, status: NodeStatus) -> Result<()> {
+		require_authorized(&ctx.accounts.node_record, &ctx.accounts.config, ctx.accounts.signer.key)?;
+
+		ctx.accounts.node_record.status = status;
+		Ok(())
+	}
+}
+
+fn require_authorized(
+	node_record: &Account<NodeRecord>,
+	config: &Account<RegistryConfig>,
+	signer: &Pubkey,
+) -> Result<()> {
+	require!(
+		*signer == node_record.owner || *signer == config.authority,
+		RegistryError::Unauthorized
+	);
+	Ok(())
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct Initialize<'info> {
+	#[account(
+		init,
+		payer = authority,
+		space = 8 + RegistryConfig::INIT_SPACE,
+		seeds = [b"registry_config"],
+		bump
+	)]
+	pub config: Account<'info, RegistryConfig>,
+
+	#[account(mut)]
+	pub authority: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(node: Pubkey, metadata_uri: String)]
+pub struct RegisterNode<'info> {
+	#[account(
+		init,
+		payer = owner,
+		space = 8 + NodeRecord::INIT_SPACE,
+		seeds = [b"node", node.as_ref()],
+		bump
+	)]
+	pub node_record: Account<'info, NodeRecord>,
+
+	#[account(mut)]
+	pub owner: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MutateNodeRecord<'info> {
+	#[account(mut, seeds = [b"node", node_record.node.as_ref()], bump)]
+	pub node_record: Account<'info, NodeRecord>,
+
+	#[account(seeds = [b"registry_config"], bump)]
+	pub config: Account<'info, RegistryConfig>,
+
+	pub signer: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryConfig {
+	pub authority: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct NodeRecord {
+	pub owner: Pubkey,
+	pub node: Pubkey,
+	#[max_len(MAX_METADATA_URI_LEN)]
+	pub metadata_uri: String,
+	pub registered_at: i64,
+	pub status: NodeStatus,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum NodeStatus {
+	Active,
+	Suspended,
+	Deregistered,
+}
+
+#[event]
+pub struct NodeRegistered {
+	pub node: Pubkey,
+	pub owner: Pubkey,
+}
+
+#[error_code]
+pub enum RegistryError {
+	#[msg("Metadata URI exceeds the maximum allowed length")]
+	MetadataUriTooLong,
+	#[msg("Signer is neither the node owner nor the registry authority")]
+	Unauthorized,
+}